@@ -1,15 +1,116 @@
 use clap::Parser;
 use futures::executor::block_on;
+use http::StatusCode;
 use rand::prelude::*;
-use rusoto_core::{Region, RusotoError, credential::DefaultCredentialsProvider};
-use rusoto_s3::{GetObjectRequest, ListObjectsV2Request, PutObjectRequest, S3Client, S3};
-use std::{str::FromStr, sync::Arc, time::Instant};
+use rusoto_core::{Region, RusotoError};
+use rusoto_credential::{DefaultCredentialsProvider, InstanceMetadataProvider, StaticProvider};
+use rusoto_s3::{
+    AbortMultipartUploadRequest, CompleteMultipartUploadRequest, CompletedMultipartUpload,
+    CompletedPart, CreateMultipartUploadRequest, DeleteObjectRequest, GetObjectRequest,
+    ListObjectsV2Request, PutObjectRequest, S3Client, UploadPartRequest, S3,
+};
+use rusoto_sts::WebIdentityProvider;
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    io::Write,
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 use tokio::io::AsyncReadExt;
 
+/// S3 requires every part of a multipart upload except the last to be at least 5 MiB.
+const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// Credential provider used to build the `S3Client`. Mirrors the provider set real object-store
+/// clients support, so the benchmark can run against S3-compatible systems and cloud instance
+/// roles without ambient credentials being exported.
+#[derive(clap::ValueEnum, Clone, Debug)]
+#[value(rename_all = "kebab-case")]
+enum AuthMode {
+    /// `DefaultCredentialsProvider`: environment, profile, then instance metadata.
+    Default,
+    /// `StaticProvider` built from `--access-key`/`--secret-key`.
+    Static,
+    /// `InstanceMetadataProvider`, for EC2 instance roles.
+    InstanceMetadata,
+    /// `WebIdentityProvider`, reading the token file and role ARN from the environment.
+    WebIdentity,
+}
+
 #[derive(Debug)]
 enum RequestType {
     Put,
+    MultipartPut,
+    /// A single `upload_part` call within a multipart upload, so part latency can be compared
+    /// across part sizes independently of the whole-upload timing recorded as `MultipartPut`.
+    MultipartPutPart,
     Get,
+    RangeGet,
+    Delete,
+}
+
+/// Relative weights of the three operations a mixed workload worker picks between on each
+/// iteration, parsed from a `--workload-mix` string like `put=50,get=40,delete=10`.
+#[derive(Debug, Clone, Copy)]
+struct WorkloadMix {
+    put: u32,
+    get: u32,
+    delete: u32,
+}
+
+impl WorkloadMix {
+    fn total(&self) -> u32 {
+        self.put + self.get + self.delete
+    }
+
+    /// Picks an operation for `roll` drawn from `0..self.total()`.
+    fn pick(&self, roll: u32) -> RequestType {
+        if roll < self.put {
+            RequestType::Put
+        } else if roll < self.put + self.get {
+            RequestType::Get
+        } else {
+            RequestType::Delete
+        }
+    }
+}
+
+impl FromStr for WorkloadMix {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mix = WorkloadMix {
+            put: 0,
+            get: 0,
+            delete: 0,
+        };
+        for entry in s.split(',') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let (op, weight) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("invalid workload-mix entry: {}", entry))?;
+            let weight: u32 = weight
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid weight in workload-mix entry: {}", entry))?;
+            match op.trim() {
+                "put" => mix.put = weight,
+                "get" => mix.get = weight,
+                "delete" => mix.delete = weight,
+                other => return Err(format!("unknown workload-mix operation: {}", other)),
+            }
+        }
+        if mix.total() == 0 {
+            return Err("workload-mix must have at least one non-zero weight".to_string());
+        }
+        Ok(mix)
+    }
 }
 
 #[derive(Debug)]
@@ -18,6 +119,12 @@ struct Stats {
     end_time: Instant,
     request_type: RequestType,
     file_size: usize,
+    /// Number of parts the upload was split into. Only set for `RequestType::MultipartPut`.
+    part_count: Option<u32>,
+    /// How many times the operation was retried before it succeeded. Always 0 for
+    /// `RequestType::MultipartPut` and `RequestType::MultipartPutPart`; retries of individual
+    /// parts are not counted.
+    retries: u32,
 }
 
 #[derive(Parser, Debug)]
@@ -44,12 +151,267 @@ struct Args {
     #[arg(long, default_value_t = 1)]
     get_count_per_thread: u32,
 
+    /// Objects larger than this many bytes are uploaded via multipart upload instead of a single `PutObject`.
+    #[arg(long, default_value_t = 8 * 1024 * 1024)]
+    multipart_threshold: u64,
+
+    /// Size of each part of a multipart upload. Clamped up to the 5 MiB minimum S3 requires.
+    #[arg(long, default_value_t = MIN_PART_SIZE as u64)]
+    part_size: u64,
+
+    /// Maximum number of attempts (including the first) before giving up on a retryable error.
+    #[arg(long, default_value_t = 3)]
+    max_retries: u32,
+
+    /// Base delay in milliseconds for exponential backoff between retries.
+    #[arg(long, default_value_t = 100)]
+    retry_base_delay_ms: u64,
+
+    /// Size in bytes of each ranged read, when a GET is selected to be ranged.
+    #[arg(long, default_value_t = 1024 * 1024, value_parser = parse_nonzero_u64)]
+    range_read_size: u64,
+
+    /// Fraction of GET operations, in 0.0..=1.0, that issue a ranged read instead of reading the
+    /// whole object.
+    #[arg(long, default_value_t = 0.0, value_parser = parse_ratio)]
+    range_read_ratio: f64,
+
+    /// Credential provider to authenticate against S3 with.
+    #[arg(long, value_enum, default_value = "default")]
+    auth_mode: AuthMode,
+
+    /// Access key, used when `--auth-mode static`.
+    #[arg(long)]
+    access_key: Option<String>,
+
+    /// Secret key, used when `--auth-mode static`.
+    #[arg(long)]
+    secret_key: Option<String>,
+
+    /// Compute an MD5 digest of each PUT body and re-check it on GET to detect corruption. Only
+    /// full-object GETs of the GET phase are re-checked; ranged GETs and GETs of the
+    /// `--workload-mix` workload are not.
+    #[arg(long, default_value_t = false)]
+    verify_checksums: bool,
+
+    /// Write one CSV row per recorded request (request type, start offset ms, duration ms, size bytes,
+    /// part count; the part count is empty except for multipart uploads).
+    #[arg(long)]
+    output_csv: Option<PathBuf>,
+
+    /// Weighted mix of put/get/delete operations for the mixed workload, e.g. `put=50,get=40,delete=10`.
+    /// When set, spawns `--workload-concurrency` worker tasks that each pick an operation by weight
+    /// instead of running the separate PUT and GET phases.
+    #[arg(long)]
+    workload_mix: Option<WorkloadMix>,
+
+    #[arg(long, default_value_t = 0)]
+    workload_concurrency: u32,
+
+    #[arg(long, default_value_t = 0)]
+    workload_count_per_thread: u32,
+
     #[arg(short, long, default_value_t = false)]
     verbose: bool,
 }
 
+/// Parses a `--range-read-ratio`-style fraction, rejecting anything outside `0.0..=1.0` so it's
+/// always safe to pass to `rand::Rng::gen_bool`.
+fn parse_ratio(s: &str) -> Result<f64, String> {
+    let value: f64 = s.parse().map_err(|_| format!("invalid ratio: {}", s))?;
+    if (0.0..=1.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!("ratio must be between 0.0 and 1.0, got {}", value))
+    }
+}
+
+/// Parses a `--range-read-size`-style byte count, rejecting `0` so `start + range_read_size - 1`
+/// can't underflow when computing a range's end offset.
+fn parse_nonzero_u64(s: &str) -> Result<u64, String> {
+    let value: u64 = s.parse().map_err(|_| format!("invalid size: {}", s))?;
+    if value == 0 {
+        Err("range-read-size must be greater than 0".to_string())
+    } else {
+        Ok(value)
+    }
+}
+
+/// Returns the value at percentile `p` (0..=100) of an already-sorted slice, using
+/// the element whose index is `p` percent of the way from the first to the last, rounded to the
+/// nearest index. Returns 0 for an empty slice.
+fn percentile(sorted: &[u128], p: f64) -> u128 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let idx = ((p / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+    sorted[idx.min(sorted.len() - 1)]
+}
+
+/// Whether an error is worth retrying: a dispatch-level failure, a 5xx from the server, or
+/// throttling. Only inspects variants that don't depend on the request's associated error type,
+/// so this works uniformly across `put_object`, `get_object`, etc.
+fn is_retryable<E>(err: &RusotoError<E>) -> bool {
+    match err {
+        RusotoError::HttpDispatch(_) => true,
+        RusotoError::Unknown(resp) => {
+            resp.status.is_server_error() || resp.status == StatusCode::TOO_MANY_REQUESTS
+        }
+        _ => false,
+    }
+}
+
+/// Runs `op` up to `max_retries` times in total (the first attempt included), retrying only on
+/// retryable errors and sleeping `base_delay * 2^attempt`
+/// plus random jitter in `0..base_delay` between attempts. Returns the final result along with how
+/// many retries were needed and the start/end time of only the final attempt, so retry backoff is
+/// excluded from the reported latency.
+async fn retry_with_backoff<T, E, F, Fut>(
+    max_retries: u32,
+    base_delay: Duration,
+    mut op: F,
+) -> (Result<T, RusotoError<E>>, u32, Instant, Instant)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, RusotoError<E>>>,
+{
+    let mut attempt = 0;
+    loop {
+        let attempt_start = Instant::now();
+        match op().await {
+            Ok(v) => return (Ok(v), attempt, attempt_start, Instant::now()),
+            Err(e) if attempt + 1 < max_retries && is_retryable(&e) => {
+                attempt += 1;
+                let backoff = base_delay * 2u32.pow(attempt - 1);
+                let jitter_ms = thread_rng().gen_range(0..base_delay.as_millis().max(1) as u64);
+                tokio::time::sleep(backoff + Duration::from_millis(jitter_ms)).await;
+            }
+            Err(e) => return (Err(e), attempt, attempt_start, Instant::now()),
+        }
+    }
+}
+
+/// Per-part timing recorded by [`multipart_put`]: the start/end instant of the `upload_part`
+/// call and the size of the chunk it uploaded.
+struct PartTiming {
+    start_time: Instant,
+    end_time: Instant,
+    size: usize,
+}
+
+/// Uploads `body` as a multipart upload split into chunks of `part_size` bytes, returning the
+/// number of parts and the per-part timings on success. Each `upload_part` call is retried with
+/// the same backoff policy as the single-object PUT/GET/DELETE paths; `create_multipart_upload`
+/// and `complete_multipart_upload` are not retried since they run once per upload rather than
+/// once per part. Aborts the upload on any failure so no dangling upload is left behind on the
+/// server.
+async fn multipart_put(
+    s3: &S3Client,
+    bucket: &str,
+    key: &str,
+    body: Vec<u8>,
+    part_size: usize,
+    max_retries: u32,
+    retry_base_delay: Duration,
+) -> Result<(u32, Vec<PartTiming>), String> {
+    let part_size = part_size.max(MIN_PART_SIZE);
+
+    let create_resp = s3
+        .create_multipart_upload(CreateMultipartUploadRequest {
+            bucket: bucket.to_string(),
+            key: key.to_string(),
+            ..Default::default()
+        })
+        .await
+        .map_err(|e| format!("create_multipart_upload failed: {:?}", e))?;
+    let upload_id = create_resp
+        .upload_id
+        .ok_or_else(|| "create_multipart_upload returned no upload_id".to_string())?;
+
+    let mut completed_parts = Vec::new();
+    let mut part_timings = Vec::new();
+    let mut upload_err = None;
+    for (i, chunk) in body.chunks(part_size).enumerate() {
+        let part_number = (i + 1) as i64;
+        let (result, _retries, part_start, part_end) = retry_with_backoff(
+            max_retries,
+            retry_base_delay,
+            || {
+                let s3 = s3.clone();
+                let upload_id = upload_id.clone();
+                let part_req = UploadPartRequest {
+                    bucket: bucket.to_string(),
+                    key: key.to_string(),
+                    upload_id,
+                    part_number,
+                    body: Some(chunk.to_vec().into()),
+                    ..Default::default()
+                };
+                async move { s3.upload_part(part_req).await }
+            },
+        )
+        .await;
+        match result {
+            Ok(resp) => {
+                part_timings.push(PartTiming {
+                    start_time: part_start,
+                    end_time: part_end,
+                    size: chunk.len(),
+                });
+                completed_parts.push(CompletedPart {
+                    e_tag: resp.e_tag,
+                    part_number: Some(part_number),
+                })
+            }
+            Err(e) => {
+                upload_err = Some(format!("upload_part {} failed: {:?}", part_number, e));
+                break;
+            }
+        }
+    }
+
+    if let Some(err) = upload_err {
+        let _ = s3
+            .abort_multipart_upload(AbortMultipartUploadRequest {
+                bucket: bucket.to_string(),
+                key: key.to_string(),
+                upload_id,
+                ..Default::default()
+            })
+            .await;
+        return Err(err);
+    }
+
+    let part_count = completed_parts.len() as u32;
+    if let Err(e) = s3
+        .complete_multipart_upload(CompleteMultipartUploadRequest {
+            bucket: bucket.to_string(),
+            key: key.to_string(),
+            upload_id: upload_id.clone(),
+            multipart_upload: Some(CompletedMultipartUpload {
+                parts: Some(completed_parts),
+            }),
+            ..Default::default()
+        })
+        .await
+    {
+        let _ = s3
+            .abort_multipart_upload(AbortMultipartUploadRequest {
+                bucket: bucket.to_string(),
+                key: key.to_string(),
+                upload_id,
+                ..Default::default()
+            })
+            .await;
+        return Err(format!("complete_multipart_upload failed: {:?}", e));
+    }
+
+    Ok((part_count, part_timings))
+}
+
 #[tokio::main]
 async fn main() {
+    let program_start = Instant::now();
     let args = Args::parse();
 
     let endpoint = args.endpoint;
@@ -59,55 +421,164 @@ async fn main() {
     let put_count_per_thread = args.put_count_per_thread;
     let get_concurrency = args.get_concurrency;
     let get_count_per_thread = args.get_count_per_thread;
+    let multipart_threshold = args.multipart_threshold as usize;
+    let part_size = args.part_size as usize;
+    let max_retries = args.max_retries;
+    let retry_base_delay = Duration::from_millis(args.retry_base_delay_ms);
+    let range_read_size = args.range_read_size;
+    let range_read_ratio = args.range_read_ratio;
+    let output_csv = args.output_csv;
+    let workload_mix = args.workload_mix;
+    let workload_concurrency = args.workload_concurrency;
+    let workload_count_per_thread = args.workload_count_per_thread;
 
     let verbose = args.verbose;
 
     let region = Region::from_str(endpoint.as_str()).unwrap();
-    let credentials = DefaultCredentialsProvider::new().unwrap();
-    let s3 = S3Client::new_with(
-        rusoto_core::request::HttpClient::new().unwrap(),
-        credentials,
-        region,
-    );
+    let s3 = match args.auth_mode {
+        AuthMode::Default => {
+            let credentials = DefaultCredentialsProvider::new().unwrap();
+            S3Client::new_with(
+                rusoto_core::request::HttpClient::new().unwrap(),
+                credentials,
+                region,
+            )
+        }
+        AuthMode::Static => {
+            let access_key = args
+                .access_key
+                .expect("--access-key is required for --auth-mode static");
+            let secret_key = args
+                .secret_key
+                .expect("--secret-key is required for --auth-mode static");
+            let credentials = StaticProvider::new_minimal(access_key, secret_key);
+            S3Client::new_with(
+                rusoto_core::request::HttpClient::new().unwrap(),
+                credentials,
+                region,
+            )
+        }
+        AuthMode::InstanceMetadata => {
+            let credentials = InstanceMetadataProvider::new();
+            S3Client::new_with(
+                rusoto_core::request::HttpClient::new().unwrap(),
+                credentials,
+                region,
+            )
+        }
+        AuthMode::WebIdentity => {
+            let credentials = WebIdentityProvider::from_k8s_env();
+            S3Client::new_with(
+                rusoto_core::request::HttpClient::new().unwrap(),
+                credentials,
+                region,
+            )
+        }
+    };
     // let s3 = S3Client::new(Region::from_str(endpoint.as_str()).unwrap());
 
+    let verify_checksums = args.verify_checksums;
+    let expected_digests: Arc<Mutex<HashMap<String, [u8; 16]>>> = Arc::new(Mutex::new(HashMap::new()));
+    let checksum_mismatches = Arc::new(std::sync::atomic::AtomicU32::new(0));
+
     let stats_vec = Arc::new(std::sync::Mutex::new(Vec::new()));
 
     let mut tasks_future = Vec::new();
 
-    // spawn put threads
-    for _ in 0..put_concurrency {
+    // spawn put threads (skipped when --workload-mix replaces the separate PUT/GET phases)
+    for _ in 0..(if workload_mix.is_none() { put_concurrency } else { 0 }) {
         let s3 = s3.clone();
         let stats_vec = Arc::clone(&stats_vec);
         let bucket = bucket.clone();
         let root_prefix = root_prefix.clone();
+        let expected_digests = Arc::clone(&expected_digests);
         let put_task_future = tokio::task::spawn(async move {
             for _ in 0..put_count_per_thread {
                 let file_size = thread_rng().gen_range(1024..1024 * 1024 * 100);
                 let file_name = format!("put_{}", file_size);
                 let key = format!("{}/{}", root_prefix, file_name);
                 let body: Vec<u8> = (0..file_size).map(|_| thread_rng().gen()).collect();
-                let start_time = Instant::now();
-                let put_req = PutObjectRequest {
-                    bucket: bucket.clone(),
-                    key: key.clone(),
-                    body: Some(body.into()),
-                    ..Default::default()
+                let content_md5 = if verify_checksums {
+                    let digest = md5::compute(&body);
+                    expected_digests.lock().unwrap().insert(key.clone(), digest.0);
+                    Some(base64::encode(digest.0))
+                } else {
+                    None
                 };
-                match s3.put_object(put_req).await {
+
+                if file_size > multipart_threshold {
+                    let start_time = Instant::now();
+                    match multipart_put(&s3, &bucket, &key, body, part_size, max_retries, retry_base_delay).await {
+                        Ok((part_count, part_timings)) => {
+                            let end_time = Instant::now();
+                            let stats = Stats {
+                                start_time,
+                                end_time,
+                                request_type: RequestType::MultipartPut,
+                                file_size,
+                                part_count: Some(part_count),
+                                retries: 0,
+                            };
+                            if verbose {
+                                println!(
+                                    "multipart put key {} ({} parts) takes {}ms",
+                                    key,
+                                    part_count,
+                                    end_time.duration_since(start_time).as_millis()
+                                );
+                            }
+                            let mut stats_vec = stats_vec.lock().unwrap();
+                            stats_vec.push(stats);
+                            for part in part_timings {
+                                stats_vec.push(Stats {
+                                    start_time: part.start_time,
+                                    end_time: part.end_time,
+                                    request_type: RequestType::MultipartPutPart,
+                                    file_size: part.size,
+                                    part_count: None,
+                                    retries: 0,
+                                });
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("Error multipart putting object {}: {}", key, e);
+                        }
+                    }
+                    continue;
+                }
+
+                let (result, retries, start_time, end_time) = retry_with_backoff(
+                    max_retries,
+                    retry_base_delay,
+                    || {
+                        let s3 = s3.clone();
+                        let put_req = PutObjectRequest {
+                            bucket: bucket.clone(),
+                            key: key.clone(),
+                            body: Some(body.clone().into()),
+                            content_md5: content_md5.clone(),
+                            ..Default::default()
+                        };
+                        async move { s3.put_object(put_req).await }
+                    },
+                )
+                .await;
+                match result {
                     Ok(_) => {
-                        let end_time = Instant::now();
                         let stats = Stats {
                             start_time,
                             end_time,
                             request_type: RequestType::Put,
                             file_size,
+                            part_count: None,
+                            retries,
                         };
                         if verbose {
                             println!(
-                                "put key {} takes {}ms",
+                                "put key {} takes {}ms ({} retries)",
                                 key,
-                                end_time.duration_since(start_time).as_millis()
+                                end_time.duration_since(start_time).as_millis(),
+                                retries
                             );
                         }
                         stats_vec.lock().unwrap().push(stats);
@@ -124,12 +595,14 @@ async fn main() {
         tasks_future.push(put_task_future);
     }
 
-    // spawn get threads
-    for _ in 0..get_concurrency {
+    // spawn get threads (skipped when --workload-mix replaces the separate PUT/GET phases)
+    for _ in 0..(if workload_mix.is_none() { get_concurrency } else { 0 }) {
         let s3 = s3.clone();
         let stats_vec = Arc::clone(&stats_vec);
         let bucket = bucket.clone();
         let root_prefix = root_prefix.clone();
+        let expected_digests = Arc::clone(&expected_digests);
+        let checksum_mismatches = Arc::clone(&checksum_mismatches);
 
         let get_task_future = tokio::task::spawn(async move {
             let mut get_num = 0;
@@ -169,42 +642,87 @@ async fn main() {
                     continue;
                 }
                 get_num += 1;
-                let key = objects[thread_rng().gen_range(0..objects.len())]
-                    .key
-                    .clone()
-                    .unwrap();
-                let start_time = Instant::now();
-                let get_req = GetObjectRequest {
-                    bucket: bucket.clone(),
-                    key: key.clone(),
-                    ..Default::default()
+                let object = &objects[thread_rng().gen_range(0..objects.len())];
+                let key = object.key.clone().unwrap();
+                let object_size = object.size.unwrap_or(0).max(0) as u64;
+
+                let is_range_read = range_read_ratio > 0.0 && thread_rng().gen_bool(range_read_ratio);
+                let range = if is_range_read && object_size > range_read_size {
+                    let start = thread_rng().gen_range(0..object_size);
+                    let end = (start + range_read_size - 1).min(object_size - 1);
+                    Some(format!("bytes={}-{}", start, end))
+                } else {
+                    None
                 };
-                match s3.get_object(get_req).await {
-                    Ok(resp) => match resp.body {
-                        Some(body) => {
-                            let mut body = body.into_async_read();
+                let request_type = if range.is_some() {
+                    RequestType::RangeGet
+                } else {
+                    RequestType::Get
+                };
+
+                let (result, retries, start_time, end_time) = retry_with_backoff(
+                    max_retries,
+                    retry_base_delay,
+                    || {
+                        let s3 = s3.clone();
+                        let get_req = GetObjectRequest {
+                            bucket: bucket.clone(),
+                            key: key.clone(),
+                            range: range.clone(),
+                            ..Default::default()
+                        };
+                        async move {
+                            let resp = s3.get_object(get_req).await?;
+                            let has_body = resp.body.is_some();
                             let mut buf = Vec::new();
-                            body.read_to_end(&mut buf).await.unwrap();
-                            let end_time = Instant::now();
+                            if let Some(body) = resp.body {
+                                body.into_async_read()
+                                    .read_to_end(&mut buf)
+                                    .await
+                                    .unwrap();
+                            }
+                            Ok((buf, has_body))
+                        }
+                    },
+                )
+                .await;
+                match result {
+                    Ok((buf, has_body)) => {
+                        if !has_body {
+                            eprintln!("No body in response");
+                        } else {
+                            if verify_checksums && range.is_none() {
+                                if let Some(expected) =
+                                    expected_digests.lock().unwrap().get(&key)
+                                {
+                                    if md5::compute(&buf).0 != *expected {
+                                        eprintln!("checksum mismatch for key {}", key);
+                                        checksum_mismatches
+                                            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                                    }
+                                }
+                            }
                             let stats = Stats {
                                 start_time,
                                 end_time,
-                                request_type: RequestType::Get,
+                                request_type,
                                 file_size: buf.len(),
+                                part_count: None,
+                                retries,
                             };
-                            stats_vec.lock().unwrap().push(stats);
                             if verbose {
                                 println!(
-                                    "get key {} takes {}ms",
+                                    "{} key {} ({} bytes) takes {}ms ({} retries)",
+                                    if range.is_some() { "range get" } else { "get" },
                                     key,
-                                    end_time.duration_since(start_time).as_millis()
+                                    buf.len(),
+                                    end_time.duration_since(start_time).as_millis(),
+                                    retries
                                 );
                             }
+                            stats_vec.lock().unwrap().push(stats);
                         }
-                        None => {
-                            eprintln!("No body in response");
-                        }
-                    },
+                    }
                     Err(RusotoError::HttpDispatch(_)) => {}
                     Err(e) => {
                         eprintln!("Error getting object: {:?}", e);
@@ -215,44 +733,373 @@ async fn main() {
         tasks_future.push(get_task_future);
     }
 
+    // spawn mixed put/get/delete workload threads
+    if let Some(workload_mix) = workload_mix {
+        let mut seed_keys = HashSet::new();
+        {
+            let mut request = ListObjectsV2Request {
+                bucket: bucket.clone(),
+                prefix: Some(root_prefix.clone()),
+                ..Default::default()
+            };
+            loop {
+                match s3.list_objects_v2(request.clone()).await {
+                    Ok(result) => {
+                        seed_keys.extend(
+                            result
+                                .contents
+                                .unwrap_or_default()
+                                .into_iter()
+                                .filter_map(|o| o.key),
+                        );
+                        if result.next_continuation_token.is_none() {
+                            break;
+                        }
+                        request.continuation_token = result.next_continuation_token;
+                    }
+                    Err(e) => {
+                        eprintln!("Error listing objects for workload mix: {:?}", e);
+                        break;
+                    }
+                }
+            }
+        }
+        let known_keys = Arc::new(Mutex::new(seed_keys));
+
+        for _ in 0..workload_concurrency {
+            let s3 = s3.clone();
+            let stats_vec = Arc::clone(&stats_vec);
+            let bucket = bucket.clone();
+            let root_prefix = root_prefix.clone();
+            let known_keys = Arc::clone(&known_keys);
+            let expected_digests = Arc::clone(&expected_digests);
+            let workload_task_future = tokio::task::spawn(async move {
+                for _ in 0..workload_count_per_thread {
+                    let roll = thread_rng().gen_range(0..workload_mix.total());
+                    match workload_mix.pick(roll) {
+                        RequestType::Put => {
+                            let file_size = thread_rng().gen_range(1024..1024 * 1024 * 100);
+                            let key = format!("{}/put_{}", root_prefix, file_size);
+                            let body: Vec<u8> =
+                                (0..file_size).map(|_| thread_rng().gen()).collect();
+                            let content_md5 = if verify_checksums {
+                                let digest = md5::compute(&body);
+                                expected_digests.lock().unwrap().insert(key.clone(), digest.0);
+                                Some(base64::encode(digest.0))
+                            } else {
+                                None
+                            };
+
+                            if file_size > multipart_threshold {
+                                let start_time = Instant::now();
+                                match multipart_put(&s3, &bucket, &key, body, part_size, max_retries, retry_base_delay).await {
+                                    Ok((part_count, part_timings)) => {
+                                        let end_time = Instant::now();
+                                        known_keys.lock().unwrap().insert(key.clone());
+                                        let mut stats_vec = stats_vec.lock().unwrap();
+                                        stats_vec.push(Stats {
+                                            start_time,
+                                            end_time,
+                                            request_type: RequestType::MultipartPut,
+                                            file_size,
+                                            part_count: Some(part_count),
+                                            retries: 0,
+                                        });
+                                        for part in part_timings {
+                                            stats_vec.push(Stats {
+                                                start_time: part.start_time,
+                                                end_time: part.end_time,
+                                                request_type: RequestType::MultipartPutPart,
+                                                file_size: part.size,
+                                                part_count: None,
+                                                retries: 0,
+                                            });
+                                        }
+                                    }
+                                    Err(e) => {
+                                        eprintln!("Error multipart putting object {}: {}", key, e);
+                                    }
+                                }
+                                continue;
+                            }
+
+                            let (result, retries, start_time, end_time) = retry_with_backoff(
+                                max_retries,
+                                retry_base_delay,
+                                || {
+                                    let s3 = s3.clone();
+                                    let put_req = PutObjectRequest {
+                                        bucket: bucket.clone(),
+                                        key: key.clone(),
+                                        body: Some(body.clone().into()),
+                                        content_md5: content_md5.clone(),
+                                        ..Default::default()
+                                    };
+                                    async move { s3.put_object(put_req).await }
+                                },
+                            )
+                            .await;
+                            match result {
+                                Ok(_) => {
+                                    known_keys.lock().unwrap().insert(key.clone());
+                                    stats_vec.lock().unwrap().push(Stats {
+                                        start_time,
+                                        end_time,
+                                        request_type: RequestType::Put,
+                                        file_size,
+                                        part_count: None,
+                                        retries,
+                                    });
+                                }
+                                Err(e) => eprintln!("Error putting object {}: {:?}", key, e),
+                            }
+                        }
+                        RequestType::Get => {
+                            let key = {
+                                let keys = known_keys.lock().unwrap();
+                                keys.iter().choose(&mut thread_rng()).cloned()
+                            };
+                            let Some(key) = key else {
+                                tokio::time::sleep(Duration::from_millis(100)).await;
+                                continue;
+                            };
+                            let (result, retries, start_time, end_time) = retry_with_backoff(
+                                max_retries,
+                                retry_base_delay,
+                                || {
+                                    let s3 = s3.clone();
+                                    let get_req = GetObjectRequest {
+                                        bucket: bucket.clone(),
+                                        key: key.clone(),
+                                        ..Default::default()
+                                    };
+                                    async move {
+                                        let resp = s3.get_object(get_req).await?;
+                                        let mut buf = Vec::new();
+                                        if let Some(body) = resp.body {
+                                            body.into_async_read()
+                                                .read_to_end(&mut buf)
+                                                .await
+                                                .unwrap();
+                                        }
+                                        Ok(buf)
+                                    }
+                                },
+                            )
+                            .await;
+                            match result {
+                                Ok(buf) => stats_vec.lock().unwrap().push(Stats {
+                                    start_time,
+                                    end_time,
+                                    request_type: RequestType::Get,
+                                    file_size: buf.len(),
+                                    part_count: None,
+                                    retries,
+                                }),
+                                Err(e) => eprintln!("Error getting object {}: {:?}", key, e),
+                            }
+                        }
+                        RequestType::Delete => {
+                            let key = {
+                                let keys = known_keys.lock().unwrap();
+                                keys.iter().choose(&mut thread_rng()).cloned()
+                            };
+                            let Some(key) = key else {
+                                tokio::time::sleep(Duration::from_millis(100)).await;
+                                continue;
+                            };
+                            let (result, retries, start_time, end_time) = retry_with_backoff(
+                                max_retries,
+                                retry_base_delay,
+                                || {
+                                    let s3 = s3.clone();
+                                    let delete_req = DeleteObjectRequest {
+                                        bucket: bucket.clone(),
+                                        key: key.clone(),
+                                        ..Default::default()
+                                    };
+                                    async move { s3.delete_object(delete_req).await }
+                                },
+                            )
+                            .await;
+                            match result {
+                                Ok(_) => {
+                                    known_keys.lock().unwrap().remove(&key);
+                                    stats_vec.lock().unwrap().push(Stats {
+                                        start_time,
+                                        end_time,
+                                        request_type: RequestType::Delete,
+                                        file_size: 0,
+                                        part_count: None,
+                                        retries,
+                                    });
+                                }
+                                Err(e) => eprintln!("Error deleting object {}: {:?}", key, e),
+                            }
+                        }
+                        RequestType::MultipartPut
+                        | RequestType::MultipartPutPart
+                        | RequestType::RangeGet => unreachable!(),
+                    }
+                }
+            });
+            tasks_future.push(workload_task_future);
+        }
+    }
+
     let _results = block_on(futures::future::join_all(tasks_future));
+    let wall_clock_secs = program_start.elapsed().as_secs_f64();
 
-    let mut put_count = 0;
-    let mut get_count = 0;
-    let mut put_time = 0;
-    let mut get_time = 0;
+    let mut put_durations = Vec::new();
+    let mut get_durations = Vec::new();
+    let mut multipart_put_durations = Vec::new();
+    let mut multipart_put_part_durations = Vec::new();
+    let mut range_get_durations = Vec::new();
+    let mut delete_durations = Vec::new();
     let mut put_file_size = 0;
     let mut get_file_size = 0;
+    let mut multipart_put_file_size = 0;
+    let mut multipart_put_part_count: u128 = 0;
+    let mut multipart_put_part_file_size = 0;
+    let mut range_get_file_size = 0;
+    let mut put_retried_count = 0;
+    let mut get_retried_count = 0;
+    let mut range_get_retried_count = 0;
+    let mut delete_retried_count = 0;
     let stat_vec = stats_vec.lock().unwrap();
     for i in stat_vec.iter() {
+        let duration_ms = i.end_time.duration_since(i.start_time).as_millis();
         match i.request_type {
             RequestType::Put => {
-                put_count += 1;
-                put_time += i.end_time.duration_since(i.start_time).as_millis() as u128;
+                put_durations.push(duration_ms);
                 put_file_size += i.file_size;
+                if i.retries > 0 {
+                    put_retried_count += 1;
+                }
+            }
+            RequestType::MultipartPut => {
+                multipart_put_durations.push(duration_ms);
+                multipart_put_file_size += i.file_size;
+                multipart_put_part_count += i.part_count.unwrap_or(0) as u128;
+            }
+            RequestType::MultipartPutPart => {
+                multipart_put_part_durations.push(duration_ms);
+                multipart_put_part_file_size += i.file_size;
             }
             RequestType::Get => {
-                get_count += 1;
-                get_time += i.end_time.duration_since(i.start_time).as_millis() as u128;
+                get_durations.push(duration_ms);
                 get_file_size += i.file_size;
+                if i.retries > 0 {
+                    get_retried_count += 1;
+                }
+            }
+            RequestType::RangeGet => {
+                range_get_durations.push(duration_ms);
+                range_get_file_size += i.file_size;
+                if i.retries > 0 {
+                    range_get_retried_count += 1;
+                }
             }
+            RequestType::Delete => {
+                delete_durations.push(duration_ms);
+                if i.retries > 0 {
+                    delete_retried_count += 1;
+                }
+            }
+        }
+    }
+    put_durations.sort_unstable();
+    get_durations.sort_unstable();
+    multipart_put_durations.sort_unstable();
+    multipart_put_part_durations.sort_unstable();
+    range_get_durations.sort_unstable();
+    delete_durations.sort_unstable();
+
+    let report = |label: &str,
+                  durations: &[u128],
+                  total_size: usize,
+                  retried_count: Option<u32>| {
+        if durations.is_empty() {
+            return;
         }
+        let count = durations.len();
+        let mean = durations.iter().sum::<u128>() / count as u128;
+        let throughput_mbps = (total_size as f64 / 1024.0 / 1024.0) / wall_clock_secs;
+        let retry_suffix = match retried_count {
+            Some(retried) => format!(
+                ", retried={} ({:.2}%)",
+                retried,
+                retried as f64 / count as f64 * 100.0
+            ),
+            None => String::new(),
+        };
+        println!(
+            "{} stats: count={}, total_size={} MB, throughput={:.2} MB/s, mean={}ms, p50={}ms, p90={}ms, p99={}ms, max={}ms{}",
+            label,
+            count,
+            total_size / 1024 / 1024,
+            throughput_mbps,
+            mean,
+            percentile(durations, 50.0),
+            percentile(durations, 90.0),
+            percentile(durations, 99.0),
+            durations.last().unwrap(),
+            retry_suffix
+        );
+    };
+    report("PUT", &put_durations, put_file_size, Some(put_retried_count));
+    report(
+        "MULTIPART PUT",
+        &multipart_put_durations,
+        multipart_put_file_size,
+        None,
+    );
+    if !multipart_put_durations.is_empty() {
+        println!(
+            "MULTIPART PUT part_count: total={}, mean_parts_per_upload={:.1}",
+            multipart_put_part_count,
+            multipart_put_part_count as f64 / multipart_put_durations.len() as f64
+        );
     }
-    let put_avg_time = put_time / put_count as u128;
-    let get_avg_time = get_time / get_count as u128;
-
-    println!(
-        "PUT stats: count={}, total_time={}ms, avg_time={}ms, total_size={} MB",
-        put_count,
-        put_time,
-        put_avg_time,
-        put_file_size / 1024 / 1024
+    report(
+        "MULTIPART PUT PART",
+        &multipart_put_part_durations,
+        multipart_put_part_file_size,
+        None,
     );
-    println!(
-        "GET stats: count={}, total_time={}ms, avg_time={}ms, total_size={} MB",
-        get_count,
-        get_time,
-        get_avg_time,
-        get_file_size / 1024 / 1024
+    report("GET", &get_durations, get_file_size, Some(get_retried_count));
+    report(
+        "RANGE GET",
+        &range_get_durations,
+        range_get_file_size,
+        Some(range_get_retried_count),
     );
+    report("DELETE", &delete_durations, 0, Some(delete_retried_count));
+    if verify_checksums {
+        println!(
+            "CHECKSUM stats: mismatches={}",
+            checksum_mismatches.load(std::sync::atomic::Ordering::Relaxed)
+        );
+    }
+
+    if let Some(path) = output_csv {
+        let mut file = std::fs::File::create(&path).expect("failed to create output CSV file");
+        writeln!(
+            file,
+            "request_type,start_offset_ms,duration_ms,size_bytes,part_count"
+        )
+        .unwrap();
+        for i in stat_vec.iter() {
+            writeln!(
+                file,
+                "{:?},{},{},{},{}",
+                i.request_type,
+                i.start_time.duration_since(program_start).as_millis(),
+                i.end_time.duration_since(i.start_time).as_millis(),
+                i.file_size,
+                i.part_count.map_or(String::new(), |p| p.to_string())
+            )
+            .unwrap();
+        }
+    }
 }